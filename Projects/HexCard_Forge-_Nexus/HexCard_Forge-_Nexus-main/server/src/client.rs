@@ -0,0 +1,170 @@
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+/// Exponential-backoff schedule used when re-dialing a dropped connection.
+#[derive(Clone, Copy)]
+struct Backoff {
+    current: Duration,
+    cap: Duration,
+}
+
+impl Backoff {
+    const INITIAL: Duration = Duration::from_millis(100);
+    const CAP: Duration = Duration::from_secs(30);
+
+    fn new() -> Self {
+        Self {
+            current: Self::INITIAL,
+            cap: Self::CAP,
+        }
+    }
+
+    /// Reset to the initial delay after a successful handshake.
+    fn reset(&mut self) {
+        self.current = Self::INITIAL;
+    }
+
+    /// Return the next delay (with jitter) and double the base for next time.
+    fn next_delay(&mut self) -> Duration {
+        let base = self.current;
+        self.current = (self.current * 2).min(self.cap);
+        // Full jitter in [0, base) to avoid a thundering herd of reconnects.
+        base.mul_f64(rand::random::<f64>())
+    }
+}
+
+/// A resilient websocket client.
+///
+/// Complements the server [`Session`](crate::Session): beyond a one-shot
+/// [`Client::connect_async`], [`Client::connect_with_retry`] transparently
+/// re-dials on disconnect using exponential backoff with jitter and can replay
+/// a hello message every time the socket is re-established, so callers get a
+/// handle that outlives individual connections.
+pub struct Client {
+    url: String,
+    hello: Option<Message>,
+}
+
+/// A handle over a connection that survives reconnects.
+///
+/// Frames sent with [`ClientHandle::send`] are written to whichever socket is
+/// currently live; inbound frames arrive on [`ClientHandle::recv`]. The
+/// underlying socket is transparently re-established by a background task.
+pub struct ClientHandle {
+    outbound: mpsc::Sender<Message>,
+    inbound: mpsc::Receiver<Message>,
+}
+
+impl ClientHandle {
+    /// Queue a frame to be sent on the current socket.
+    pub async fn send(&self, msg: Message) -> anyhow::Result<()> {
+        self.outbound
+            .send(msg)
+            .await
+            .map_err(|_| anyhow::anyhow!("client connection task has stopped"))
+    }
+
+    /// Receive the next inbound frame, or `None` once the client stops.
+    pub async fn recv(&mut self) -> Option<Message> {
+        self.inbound.recv().await
+    }
+}
+
+impl Client {
+    /// Create a client targeting `url` (`ws://` or `wss://`).
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            hello: None,
+        }
+    }
+
+    /// Replay `msg` on every (re)connection, immediately after the handshake.
+    pub fn on_reconnect(mut self, msg: Message) -> Self {
+        self.hello = Some(msg);
+        self
+    }
+
+    /// Dial `url` once, returning the live socket.
+    ///
+    /// The socket is a [`MaybeTlsStream`]: `wss://` URLs are terminated over TLS
+    /// when the `tls` feature is enabled, and `ws://` URLs stay plaintext, so
+    /// the same handle serves both transports.
+    pub async fn connect_async(
+        url: &str,
+    ) -> anyhow::Result<WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>> {
+        let (ws, _resp) = connect_async(url).await?;
+        Ok(ws)
+    }
+
+    /// Spawn a background task that keeps a connection alive across drops.
+    ///
+    /// Returns a [`ClientHandle`] whose sender/receiver outlive any single
+    /// socket: on disconnect the task waits a backoff interval and re-dials,
+    /// resetting the backoff after each successful handshake.
+    pub fn connect_with_retry(self) -> ClientHandle {
+        let (out_tx, out_rx) = mpsc::channel::<Message>(32);
+        let (in_tx, in_rx) = mpsc::channel::<Message>(32);
+
+        tokio::spawn(self.run(out_rx, in_tx));
+
+        ClientHandle {
+            outbound: out_tx,
+            inbound: in_rx,
+        }
+    }
+
+    async fn run(
+        self,
+        mut out_rx: mpsc::Receiver<Message>,
+        in_tx: mpsc::Sender<Message>,
+    ) {
+        let mut backoff = Backoff::new();
+        loop {
+            match Self::connect_async(&self.url).await {
+                Ok(mut ws) => {
+                    backoff.reset();
+                    if let Some(hello) = &self.hello {
+                        if ws.send(hello.clone()).await.is_err() {
+                            // Socket dropped before the hello landed; back off
+                            // rather than spinning on an immediate re-dial.
+                            tokio::time::sleep(backoff.next_delay()).await;
+                            continue;
+                        }
+                    }
+                    // Pump until the socket errors or either channel closes.
+                    loop {
+                        tokio::select! {
+                            outbound = out_rx.recv() => match outbound {
+                                Some(msg) => {
+                                    if ws.send(msg).await.is_err() {
+                                        break;
+                                    }
+                                }
+                                // Handle dropped: caller is done, stop entirely.
+                                None => return,
+                            },
+                            inbound = ws.next() => match inbound {
+                                Some(Ok(msg)) => {
+                                    if in_tx.send(msg).await.is_err() {
+                                        return;
+                                    }
+                                }
+                                Some(Err(_)) | None => break,
+                            },
+                        }
+                    }
+                    // Socket dropped; back off before re-dialing.
+                    tokio::time::sleep(backoff.next_delay()).await;
+                }
+                Err(_) => {
+                    tokio::time::sleep(backoff.next_delay()).await;
+                }
+            }
+        }
+    }
+}
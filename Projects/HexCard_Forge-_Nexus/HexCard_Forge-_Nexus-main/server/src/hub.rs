@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, Mutex};
+use tokio_tungstenite::tungstenite::Message;
+use uuid::Uuid;
+
+use crate::session::Session;
+
+/// Shared registry of every connected peer's outbound channel.
+///
+/// Each accepted connection registers its [`Session::id`] together with the
+/// sending half of an unbounded channel; the connection task owns the matching
+/// receiver and drains it onto its own websocket sink. Forwarding a frame is
+/// therefore just a map walk that pushes onto every *other* peer's sender.
+type Peers = Arc<Mutex<HashMap<Uuid, mpsc::UnboundedSender<Message>>>>;
+
+/// A fan-out websocket server.
+///
+/// Unlike the one-shot echo in [`Session::run`], a `Hub` keeps accepting
+/// connections and relays every text/binary frame received from one peer to
+/// all of the others; the sender never receives its own frame back.
+pub struct Hub {
+    peers: Peers,
+}
+
+impl Default for Hub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hub {
+    /// Create an empty hub with no connected peers.
+    pub fn new() -> Self {
+        Self {
+            peers: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Bind `addr` and serve connections until the listener errors.
+    ///
+    /// Each accepted socket is handed to [`Hub::handle`] on its own task so a
+    /// single slow or disconnecting peer never stalls the accept loop.
+    pub async fn run(&self, addr: &str) -> anyhow::Result<()> {
+        let listener = TcpListener::bind(addr).await?;
+        self.serve(listener).await
+    }
+
+    /// Serve connections on an already-bound listener until it errors.
+    ///
+    /// Useful when the caller needs the bound address up front, e.g. after
+    /// binding an ephemeral `127.0.0.1:0` port.
+    pub async fn serve(&self, listener: TcpListener) -> anyhow::Result<()> {
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let peers = Arc::clone(&self.peers);
+            tokio::spawn(async move {
+                if let Err(err) = Hub::handle(peers, stream).await {
+                    tracing::debug!(%err, "connection closed with error");
+                }
+            });
+        }
+    }
+
+    /// Deliver `msg` to every currently connected peer.
+    ///
+    /// Senders whose receiver has already been dropped are skipped; stale
+    /// entries are pruned lazily by the connection tasks themselves on
+    /// disconnect, so a failed send here is simply ignored.
+    pub async fn broadcast(&self, msg: Message) {
+        let peers = self.peers.lock().await;
+        for tx in peers.values() {
+            let _ = tx.send(msg.clone());
+        }
+    }
+
+    /// Drive a single accepted connection: register it, relay inbound frames to
+    /// the other peers, and remove it from the map on disconnect.
+    async fn handle(peers: Peers, stream: tokio::net::TcpStream) -> anyhow::Result<()> {
+        let ws = tokio_tungstenite::accept_async(stream).await?;
+        let session = Session::new();
+        let id = session.id;
+
+        let (tx, mut rx) = mpsc::unbounded_channel::<Message>();
+        peers.lock().await.insert(id, tx);
+
+        let (mut sink, mut stream) = ws.split();
+        let result: anyhow::Result<()> = loop {
+            tokio::select! {
+                // Outbound: frames other peers routed to us.
+                Some(msg) = rx.recv() => {
+                    if let Err(err) = sink.send(msg).await {
+                        break Err(err.into());
+                    }
+                }
+                // Inbound: frames from our own socket, fanned out to everyone else.
+                incoming = stream.next() => {
+                    match incoming {
+                        Some(Ok(msg)) if msg.is_text() || msg.is_binary() => {
+                            Hub::forward(&peers, id, msg).await;
+                        }
+                        Some(Ok(_)) => {}
+                        Some(Err(err)) => break Err(err.into()),
+                        None => break Ok(()),
+                    }
+                }
+            }
+        };
+
+        peers.lock().await.remove(&id);
+        result
+    }
+
+    /// Forward `msg` to every peer except `from`.
+    async fn forward(peers: &Peers, from: Uuid, msg: Message) {
+        let peers = peers.lock().await;
+        for (id, tx) in peers.iter() {
+            if *id != from {
+                let _ = tx.send(msg.clone());
+            }
+        }
+    }
+}
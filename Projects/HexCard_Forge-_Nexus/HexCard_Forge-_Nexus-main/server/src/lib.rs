@@ -0,0 +1,9 @@
+pub mod client;
+pub mod codec;
+pub mod hub;
+pub mod session;
+
+pub use client::{Client, ClientHandle};
+pub use codec::{Codec, EchoCodec};
+pub use hub::Hub;
+pub use session::Session;
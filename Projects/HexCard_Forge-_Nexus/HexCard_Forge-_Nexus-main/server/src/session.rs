@@ -1,21 +1,255 @@
+use std::time::{Duration, Instant};
+
+use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::TcpListener;
+use tokio::sync::{mpsc, oneshot};
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::tungstenite::protocol::CloseFrame;
 use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
 use uuid::Uuid;
 use futures_util::{StreamExt, SinkExt};
 
+use crate::codec::{Codec, Decoded, EchoCodec};
+
+/// Sending half of a [`Session::split`] socket.
+///
+/// Cloneable handle over an [`mpsc::Sender`] whose other end is drained by a
+/// dedicated writer task, so concurrent `send` calls from many callers are
+/// serialized onto the underlying sink without a shared lock.
+#[derive(Clone)]
+pub struct SessionWriter {
+    tx: mpsc::Sender<Message>,
+}
+
+impl SessionWriter {
+    /// Queue `msg` to be written to the socket.
+    ///
+    /// Returns an error once the writer task has shut down (e.g. after the
+    /// reader side observed a close or transport error).
+    pub async fn send(&self, msg: Message) -> anyhow::Result<()> {
+        self.tx
+            .send(msg)
+            .await
+            .map_err(|_| anyhow::anyhow!("session writer closed"))
+    }
+}
+
+/// Keepalive policy for a long-lived session.
+///
+/// A `Ping` is emitted every `interval`; if no frame of any kind arrives within
+/// `grace` the peer is considered dead and the connection is closed.
+#[derive(Clone, Copy)]
+struct Keepalive {
+    interval: Duration,
+    grace: Duration,
+}
+
 pub struct Session {
     pub id: Uuid,
+    keepalive: Option<Keepalive>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Session {
+    /// Create a session with a fresh random identifier and no keepalive.
+    pub fn new() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            keepalive: None,
+        }
+    }
+
+    /// Enable periodic `Ping` keepalives on this session.
+    ///
+    /// `interval` is how often a ping is sent; a peer that goes quiet for more
+    /// than three intervals is closed as dead.
+    pub fn with_keepalive(mut self, interval: Duration) -> Self {
+        self.keepalive = Some(Keepalive {
+            interval,
+            grace: interval * 3,
+        });
+        self
+    }
+
+    /// Split an accepted websocket into independent read and write halves.
+    ///
+    /// The sink is moved into a dedicated writer task and the stream into a
+    /// reader task, giving independent read and write halves. Callers
+    /// get back a cloneable [`SessionWriter`] for pushing unsolicited frames and
+    /// an [`mpsc::Receiver`] yielding inbound text/binary frames. A reader-side
+    /// error or close cleanly tears down the writer task.
+    pub fn split<S>(
+        self,
+        ws: WebSocketStream<S>,
+    ) -> (SessionWriter, mpsc::Receiver<Message>)
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let (mut sink, mut stream) = ws.split();
+        let (out_tx, mut out_rx) = mpsc::channel::<Message>(32);
+        let (in_tx, in_rx) = mpsc::channel::<Message>(32);
+        let (shutdown_tx, mut shutdown_rx) = oneshot::channel::<()>();
+
+        // Writer task: the sole owner of the sink, so sends are serialized.
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    msg = out_rx.recv() => match msg {
+                        Some(msg) => {
+                            if sink.send(msg).await.is_err() {
+                                break;
+                            }
+                        }
+                        None => break,
+                    },
+                    _ = &mut shutdown_rx => break,
+                }
+            }
+            let _ = sink.close().await;
+        });
+
+        // Reader task: forwards inbound frames and signals shutdown on exit.
+        tokio::spawn(async move {
+            while let Some(msg) = stream.next().await {
+                match msg {
+                    Ok(msg) if msg.is_text() || msg.is_binary() => {
+                        if in_tx.send(msg).await.is_err() {
+                            break;
+                        }
+                    }
+                    Ok(Message::Close(_)) => break,
+                    Ok(_) => {}
+                    Err(_) => break,
+                }
+            }
+            let _ = shutdown_tx.send(());
+        });
+
+        (SessionWriter { tx: out_tx }, in_rx)
+    }
+
     pub async fn run(addr: &str) -> anyhow::Result<()> {
+        Session::new().serve(addr).await
+    }
+
+    /// Accept a single connection on `addr` and echo it, honouring any
+    /// configured keepalive policy.
+    pub async fn serve(self, addr: &str) -> anyhow::Result<()> {
         let listener = TcpListener::bind(addr).await?;
         let (stream, _) = listener.accept().await?;
         let mut ws = tokio_tungstenite::accept_async(stream).await?;
+
+        // Without keepalive the loop is the original read-and-echo, now routed
+        // through the identity codec.
+        let Some(keepalive) = self.keepalive else {
+            return Self::codec_loop(ws, EchoCodec).await;
+        };
+
+        let mut ticker = tokio::time::interval(keepalive.interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut last_seen = Instant::now();
+        let mut ping_seq: u64 = 0;
+
+        loop {
+            tokio::select! {
+                incoming = ws.next() => {
+                    let Some(msg) = incoming else { break };
+                    let msg = msg?;
+                    last_seen = Instant::now();
+                    match msg {
+                        Message::Text(text) => ws.send(Message::Text(text)).await?,
+                        Message::Binary(bin) => ws.send(Message::Binary(bin)).await?,
+                        // Answer pings explicitly so we record liveness even
+                        // though tungstenite would reply at the protocol layer.
+                        Message::Ping(payload) => ws.send(Message::Pong(payload)).await?,
+                        Message::Pong(_) => {}
+                        Message::Close(_) => break,
+                        Message::Frame(_) => {}
+                    }
+                }
+                _ = ticker.tick() => {
+                    if last_seen.elapsed() > keepalive.grace {
+                        ws.send(Message::Close(Some(CloseFrame {
+                            code: CloseCode::Normal,
+                            reason: "keepalive timeout".into(),
+                        }))).await?;
+                        break;
+                    }
+                    ping_seq += 1;
+                    ws.send(Message::Ping(ping_seq.to_be_bytes().to_vec())).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Accept a single `wss://` connection on `addr`, terminating TLS with the
+    /// supplied rustls server configuration.
+    ///
+    /// The accepted [`TcpStream`](tokio::net::TcpStream) is wrapped in a
+    /// `tokio-rustls` acceptor before the websocket handshake; because the echo
+    /// loop is generic over `AsyncRead + AsyncWrite`, the same logic serves both
+    /// plain and encrypted transports unchanged.
+    #[cfg(feature = "tls")]
+    pub async fn run_tls(
+        addr: &str,
+        tls_config: tokio_rustls::rustls::ServerConfig,
+    ) -> anyhow::Result<()> {
+        use std::sync::Arc;
+
+        let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(tls_config));
+        let listener = TcpListener::bind(addr).await?;
+        let (stream, _) = listener.accept().await?;
+        let tls = acceptor.accept(stream).await?;
+        let ws = tokio_tungstenite::accept_async(tls).await?;
+        Self::codec_loop(ws, EchoCodec).await
+    }
+
+    /// Accept a single connection and echo it through a pluggable [`Codec`].
+    ///
+    /// Data frames are decoded into the codec's `Frame` type and re-encoded back
+    /// to the peer; text frames a binary codec rejects trigger a protocol close;
+    /// control frames are answered or observed in place.
+    pub async fn serve_with<C>(self, addr: &str, codec: C) -> anyhow::Result<()>
+    where
+        C: Codec,
+    {
+        let listener = TcpListener::bind(addr).await?;
+        let (stream, _) = listener.accept().await?;
+        let ws = tokio_tungstenite::accept_async(stream).await?;
+        Self::codec_loop(ws, codec).await
+    }
+
+    async fn codec_loop<S, C>(
+        mut ws: WebSocketStream<S>,
+        codec: C,
+    ) -> anyhow::Result<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+        C: Codec,
+    {
         while let Some(msg) = ws.next().await {
-            let msg = msg?;
-            if msg.is_text() || msg.is_binary() {
-                ws.send(Message::Text(msg.into_text()?)).await?;
+            match codec.decode(msg?)? {
+                Decoded::Frame(frame) => ws.send(codec.encode(&frame)?).await?,
+                Decoded::Control(Message::Ping(payload)) => {
+                    ws.send(Message::Pong(payload)).await?
+                }
+                Decoded::Control(Message::Close(_)) => break,
+                Decoded::Control(_) => {}
+                Decoded::RejectedText(_) => {
+                    ws.send(Message::Close(Some(CloseFrame {
+                        code: CloseCode::Unsupported,
+                        reason: "text frames are not accepted".into(),
+                    })))
+                    .await?;
+                    break;
+                }
             }
         }
         Ok(())
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use synflict::Hub;
+use tokio::net::TcpListener;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
+
+/// Bind an ephemeral port, start a hub on it, and return the `ws://` URL.
+async fn start_hub() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let hub = Arc::new(Hub::new());
+    tokio::spawn(async move {
+        let _ = hub.serve(listener).await;
+    });
+    format!("ws://{addr}")
+}
+
+#[tokio::test]
+async fn frame_reaches_other_peers_but_not_sender() {
+    let url = start_hub().await;
+
+    let (mut a, _) = connect_async(&url).await.unwrap();
+    let (mut b, _) = connect_async(&url).await.unwrap();
+    let (mut c, _) = connect_async(&url).await.unwrap();
+
+    // Give every peer time to register before the first broadcast.
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    a.send(Message::Text("hello".into())).await.unwrap();
+
+    let b_msg = b.next().await.unwrap().unwrap();
+    let c_msg = c.next().await.unwrap().unwrap();
+    assert_eq!(b_msg, Message::Text("hello".into()));
+    assert_eq!(c_msg, Message::Text("hello".into()));
+
+    // The sender must not receive its own frame echoed back.
+    let echoed = tokio::time::timeout(Duration::from_millis(200), a.next()).await;
+    assert!(echoed.is_err(), "sender should not be echoed its own frame");
+}
+
+#[tokio::test]
+async fn sender_gets_nothing_back_after_only_peer_disconnects() {
+    let url = start_hub().await;
+
+    let (mut a, _) = connect_async(&url).await.unwrap();
+    let (mut b, _) = connect_async(&url).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    // b leaves; the hub must remove it from the peer map.
+    b.close(None).await.unwrap();
+    drop(b);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    // With only `a` left, its frame reaches nobody and never bounces back.
+    a.send(Message::Text("alone".into())).await.unwrap();
+    let bounced = tokio::time::timeout(Duration::from_millis(200), a.next()).await;
+    assert!(bounced.is_err(), "no peers remain to receive the frame");
+}
+
+#[tokio::test]
+async fn delivers_to_n_clients() {
+    let url = start_hub().await;
+
+    let (mut sender, _) = connect_async(&url).await.unwrap();
+    let mut receivers = Vec::new();
+    for _ in 0..4 {
+        let (ws, _) = connect_async(&url).await.unwrap();
+        receivers.push(ws);
+    }
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    sender.send(Message::Binary(vec![1, 2, 3])).await.unwrap();
+
+    for rx in receivers.iter_mut() {
+        let msg = rx.next().await.unwrap().unwrap();
+        assert_eq!(msg, Message::Binary(vec![1, 2, 3]));
+    }
+}